@@ -0,0 +1,55 @@
+//! Minimal `Read`/`Seek`/`Write`-equivalent traits, used instead of [`std::io`] when the `std` feature is disabled.
+//!
+//! Only the small subset of methods this crate actually needs is provided. A `no_std` build can not rely on
+//! `io_partition`, which hard-depends on `std::io`, so [`Sir0`](crate::Sir0) remains gated behind the `std` feature;
+//! everything else, including [`Sir0Writer`](crate::Sir0Writer), works against these traits instead.
+
+use alloc::fmt;
+
+/// A position to seek from, mirroring [`std::io::SeekFrom`].
+#[derive(Debug, Clone, Copy)]
+pub enum SeekFrom {
+    /// Seek from the start of the stream.
+    Start(u64),
+    /// Seek from the end of the stream.
+    End(i64),
+    /// Seek from the current position of the stream.
+    Current(i64),
+}
+
+/// An IO error, mirroring the parts of [`std::io::Error`] this crate needs.
+#[derive(Debug)]
+pub struct Error {
+    message: &'static str,
+}
+
+impl Error {
+    /// Create a new [`Error`] carrying a static, human readable message.
+    pub fn new(message: &'static str) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Mirrors the subset of [`std::io::Write`] this crate needs.
+pub trait Write {
+    /// Write the whole buffer, or return an [`Error`].
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error>;
+}
+
+/// Mirrors the subset of [`std::io::Read`] this crate needs.
+pub trait Read {
+    /// Fill the whole buffer, or return an [`Error`].
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+}
+
+/// Mirrors the subset of [`std::io::Seek`] this crate needs.
+pub trait Seek {
+    /// Seek to the given position, returning the new absolute position.
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error>;
+}
@@ -1,8 +1,24 @@
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 //! This crate allow you to read Sir0 file, used on pokemon mystery dungeon on nintendo 3DS.
 //!
 //! The Sir0 file contain a list of pointer to various part in the file.
+//!
+//! The `std` feature is enabled by default. Disabling it (`default-features = false`) builds the crate on
+//! `no_std` + `alloc`, using the local [`io_nostd`] traits instead of `std::io`. In that configuration, [`Sir0`]
+//! is not available, as it relies on `io_partition`, which requires `std`; everything else, including
+//! [`Sir0Error`] and the pointer list codec, works on both.
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+pub mod io_nostd;
 
 mod sir0;
-pub use sir0::{Sir0, Sir0Error};
-pub use sir0::{write_sir0_footer, write_sir0_header};
+#[cfg(feature = "std")]
+pub use sir0::Sir0;
+pub use sir0::{
+    decode_sir0_pointer_list, encode_sir0_pointer_list, write_sir0_footer, write_sir0_header, FromReader,
+    Sir0Error, Sir0WriteFooterError, Sir0Writer, Sir0WriterError, ToWriter,
+};
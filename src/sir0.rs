@@ -1,30 +1,158 @@
-use byteorder::{ReadBytesExt, WriteBytesExt, LE};
-use io_partition::clone_into_vec;
+#[cfg(feature = "std")]
+use io_partition::{clone_into_vec, Partition};
+#[cfg(feature = "std")]
 use std::io::Error as IOError;
+#[cfg(feature = "std")]
 use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "std")]
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[cfg(not(feature = "std"))]
+use crate::io_nostd::{Error as IOError, Read, Seek, SeekFrom, Write};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Read little-endian primitives from a [`Read`]er, without depending on `byteorder`.
+///
+/// Implemented for every type implementing [`Read`], so it works the same whether the crate is built with the `std`
+/// feature (backed by [`std::io::Read`]) or without it (backed by [`crate::io_nostd::Read`]).
+pub trait FromReader: Read {
+    /// Read a fixed-size array of `N` bytes.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], IOError> {
+        let mut buffer = [0; N];
+        self.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Read a little-endian `u32`.
+    // `read_array` is ours, not the nightly-only `Read::read_array` this otherwise collides with.
+    #[allow(unstable_name_collisions)]
+    fn read_u32_le(&mut self) -> Result<u32, IOError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+}
+
+impl<R: Read + ?Sized> FromReader for R {}
+
+/// Write little-endian primitives to a [`Write`]r, without depending on `byteorder`.
+///
+/// Implemented for every type implementing [`Write`], so it works the same whether the crate is built with the `std`
+/// feature (backed by [`std::io::Write`]) or without it (backed by [`crate::io_nostd::Write`]).
+pub trait ToWriter: Write {
+    /// Write a little-endian `u32`.
+    fn write_u32_le(&mut self, value: u32) -> Result<(), IOError> {
+        self.write_all(&value.to_le_bytes())
+    }
+}
+
+impl<W: Write + ?Sized> ToWriter for W {}
+
 /// List all possible error that ``Sir0`` can return
+#[cfg(feature = "std")]
+#[derive(Debug, Error)]
 pub enum Sir0Error {
+    /// An error happened while performing an IO operation
     #[error("An error happened while performing an IO operation")]
     IOError(#[from] IOError),
+    /// The magic of the Sir0 file is not recognized
     #[error("The magic of the Sir0 file is not reconized: found {0:?}")]
     InvalidMagic([u8; 4]),
+    /// An error happened while creating a partition of a file
     #[error("An error happened while creating a partition of a file")]
     CreatePartitionError(#[source] IOError),
+    /// An error happened while cloning a partition of a file
     #[error("An error happened while cloning a partition of a file")]
     CloneHeaderError(#[source] IOError),
+    /// An error happened while reading data from the sir0 file
+    #[error("An error happened while reading data from the sir0 file")]
+    ReadError(#[source] IOError),
+    /// The sir0 file indicate that the pointer list of the file is after the header, which isn't supported
     #[error("the sir0 file indicate that the pointer list of the file is at offset {1}, but that the header is at {0}, after the pointer list.")]
     PointerBeforeHeader(u32, u32),
+    /// The offset of the pointer list is too big: it is either past or at the end of the file
     #[error("the offset of the pointer list ({0}) is too big: it is either past or at the end of file ({1})")]
     PointerOffsetPostOrAtFileEnd(u64, u64),
+    /// The absolute position represented by a sir0 offset overflowed a 64 bit unsigned integer
     #[error("the absolute position represented by the sir0 offset overflow the maximal capacity of an unsigned interget of 64 bit (absolute position: {0}, sum to add: {1}).")]
     AbsolutePointerOverflow(u64, u64),
+    /// The offset index passed to [`pointer_value_at`](Sir0::pointer_value_at) is out of range
+    #[error("the offset index {0} is out of range: this file only have {1} offsets")]
+    InvalidOffsetIndex(usize, usize),
+}
+
+/// List all possible error that ``Sir0`` can return
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub enum Sir0Error {
+    /// An error happened while performing an IO operation
+    IOError(IOError),
+    /// The magic of the Sir0 file is not recognized
+    InvalidMagic([u8; 4]),
+    /// An error happened while creating a partition of a file
+    CreatePartitionError(IOError),
+    /// An error happened while cloning a partition of a file
+    CloneHeaderError(IOError),
+    /// An error happened while reading data from the sir0 file
+    ReadError(IOError),
+    /// The sir0 file indicate that the pointer list of the file is after the header, which isn't supported
+    PointerBeforeHeader(u32, u32),
+    /// The offset of the pointer list is too big: it is either past or at the end of the file
+    PointerOffsetPostOrAtFileEnd(u64, u64),
+    /// The absolute position represented by a sir0 offset overflowed a 64 bit unsigned integer
+    AbsolutePointerOverflow(u64, u64),
+    /// The offset index passed to `pointer_value_at` is out of range
+    InvalidOffsetIndex(usize, usize),
+}
+
+#[cfg(not(feature = "std"))]
+impl From<IOError> for Sir0Error {
+    fn from(error: IOError) -> Self {
+        Self::IOError(error)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for Sir0Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IOError(_) => write!(f, "An error happened while performing an IO operation"),
+            Self::InvalidMagic(magic) => {
+                write!(f, "The magic of the Sir0 file is not reconized: found {:?}", magic)
+            }
+            Self::CreatePartitionError(_) => {
+                write!(f, "An error happened while creating a partition of a file")
+            }
+            Self::CloneHeaderError(_) => {
+                write!(f, "An error happened while cloning a partition of a file")
+            }
+            Self::ReadError(_) => write!(f, "An error happened while reading data from the sir0 file"),
+            Self::PointerBeforeHeader(header_offset, pointer_offset) => write!(
+                f,
+                "the sir0 file indicate that the pointer list of the file is at offset {}, but that the header is at {}, after the pointer list.",
+                pointer_offset, header_offset
+            ),
+            Self::PointerOffsetPostOrAtFileEnd(pointer_offset, file_lenght) => write!(
+                f,
+                "the offset of the pointer list ({}) is too big: it is either past or at the end of file ({})",
+                pointer_offset, file_lenght
+            ),
+            Self::AbsolutePointerOverflow(absolute_position, to_add) => write!(
+                f,
+                "the absolute position represented by the sir0 offset overflow the maximal capacity of an unsigned interget of 64 bit (absolute position: {}, sum to add: {}).",
+                absolute_position, to_add
+            ),
+            Self::InvalidOffsetIndex(index, len) => write!(
+                f,
+                "the offset index {} is out of range: this file only have {} offsets",
+                index, len
+            ),
+        }
+    }
 }
 
 /// A Sir0 file, used in pokémon mystery dungeon on 3ds and DS (only tested with the 3ds version)
 /// A Sir0 file contain a file, but have pointer to them.
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub struct Sir0<T: Read + Seek> {
     offsets: Vec<u64>,
@@ -32,18 +160,20 @@ pub struct Sir0<T: Read + Seek> {
     file: T,
 }
 
+#[cfg(feature = "std")]
 impl<T: Read + Seek> Sir0<T> {
     /// Create a new Sir0 from the file.
+    // `read_array` is ours, not the nightly-only `Read::read_array` this otherwise collides with.
+    #[allow(unstable_name_collisions)]
     pub fn new(mut file: T) -> Result<Self, Sir0Error> {
         file.seek(SeekFrom::Start(0))?;
-        let mut magic = [0; 4];
-        file.read_exact(&mut magic)?;
+        let magic = file.read_array::<4>()?;
         if magic != [b'S', b'I', b'R', b'0'] {
             return Err(Sir0Error::InvalidMagic(magic));
         };
 
-        let header_offset = file.read_u32::<LE>()?;
-        let pointer_offset = file.read_u32::<LE>()?;
+        let header_offset = file.read_u32_le()?;
+        let pointer_offset = file.read_u32_le()?;
 
         let header_lenght = pointer_offset.checked_sub(header_offset).map_or_else(
             || {
@@ -61,11 +191,6 @@ impl<T: Read + Seek> Sir0<T> {
         let file_lenght = file.seek(SeekFrom::End(0))?;
         file.seek(SeekFrom::Start(pointer_offset as u64))?;
 
-        // just a rust translation of the code from evandixon
-        let mut absolute_pointers = Vec::new();
-        let mut is_constructing = false;
-        let mut constructed_pointer: u64 = 0;
-        let mut absolute_position: u64 = 0;
         let remaining_bytes = file_lenght
             .checked_sub(pointer_offset as u64)
             .map(|n| n.checked_sub(1))
@@ -79,44 +204,12 @@ impl<T: Read + Seek> Sir0<T> {
                 },
                 Ok,
             )?;
-        for _ in 0..remaining_bytes {
-            let current = file.read_u8()?;
-            if current >= 128 {
-                is_constructing = true;
-                constructed_pointer =
-                    constructed_pointer.overflowing_shl(7).0 | ((current & 0x7F) as u64);
-            } else if is_constructing {
-                constructed_pointer =
-                    constructed_pointer.overflowing_shl(7).0 | ((current & 0x7F) as u64);
-                absolute_position = absolute_position
-                    .checked_add(constructed_pointer)
-                    .map_or_else(
-                        || {
-                            Err(Sir0Error::AbsolutePointerOverflow(
-                                absolute_position,
-                                constructed_pointer,
-                            ))
-                        },
-                        Ok,
-                    )?;
-                absolute_pointers.push(absolute_position);
-                is_constructing = false;
-                constructed_pointer = 0;
-            } else if current == 0 {
-                break;
-            } else {
-                absolute_position = absolute_position.checked_add(current as u64).map_or_else(
-                    || {
-                        Err(Sir0Error::AbsolutePointerOverflow(
-                            absolute_position,
-                            current as u64,
-                        ))
-                    },
-                    Ok,
-                )?;
-                absolute_pointers.push(absolute_position);
-            }
-        }
+
+        // read the whole pointer list in memory at once instead of doing a read (and possibly a syscall) per byte
+        let mut pointer_list_buffer = vec![0; remaining_bytes as usize];
+        file.read_exact(&mut pointer_list_buffer)?;
+
+        let absolute_pointers = decode_sir0_pointer_list(&pointer_list_buffer)?;
 
         Ok(Self {
             offsets: absolute_pointers,
@@ -144,6 +237,80 @@ impl<T: Read + Seek> Sir0<T> {
     pub fn get_file(&mut self) -> &mut T {
         &mut self.file
     }
+
+    /// read `len` bytes at the absolute position `absolute_offset` in the file, and return them.
+    pub fn read_at(&mut self, absolute_offset: u64, len: usize) -> Result<Vec<u8>, Sir0Error> {
+        clone_into_vec(&mut self.file, absolute_offset, len as u64).map_err(Sir0Error::ReadError)
+    }
+
+    /// return a bounded [`Read`] + [`Seek`] view of the file, starting at the absolute position `absolute_offset` and of length `len`.
+    pub fn sub_reader(
+        &mut self,
+        absolute_offset: u64,
+        len: u64,
+    ) -> Result<Partition<&mut T>, Sir0Error> {
+        Partition::new(&mut self.file, absolute_offset, len).map_err(Sir0Error::CreatePartitionError)
+    }
+
+    /// read the little-endian u32 stored at the position of the offset n°`offset_index` (as returned by [`offsets_get`](Self::offsets_get)).
+    pub fn pointer_value_at(&mut self, offset_index: usize) -> Result<u32, Sir0Error> {
+        let offset = *self
+            .offsets
+            .get(offset_index)
+            .ok_or(Sir0Error::InvalidOffsetIndex(offset_index, self.offsets.len()))?;
+        self.file.seek(SeekFrom::Start(offset))?;
+        Ok(self.file.read_u32_le()?)
+    }
+}
+
+/// Decode a sir0 pointer list (the footer of a sir0 file, starting at the offset-list offset stored in its header), already
+/// read in memory, into the absolute positions it describes. For a normal Sir0 file, the first 2 positions are [4, 8].
+///
+/// Decoding stops as soon as a `0` byte is encountered outside of a multi-byte value, or at the end of `data`, matching the on-disk format; this
+/// is the same decoding `Sir0::new` uses internally, exposed so tools dealing with a raw pointer list don't need to
+/// build a full [`Sir0`](crate::Sir0).
+pub fn decode_sir0_pointer_list(data: &[u8]) -> Result<Vec<u64>, Sir0Error> {
+    // just a rust translation of the code from evandixon
+    let mut absolute_pointers = Vec::new();
+    let mut is_constructing = false;
+    let mut constructed_pointer: u64 = 0;
+    let mut absolute_position: u64 = 0;
+    for &current in data {
+        if current >= 128 {
+            is_constructing = true;
+            constructed_pointer = constructed_pointer.overflowing_shl(7).0 | ((current & 0x7F) as u64);
+        } else if is_constructing {
+            constructed_pointer = constructed_pointer.overflowing_shl(7).0 | ((current & 0x7F) as u64);
+            absolute_position = absolute_position
+                .checked_add(constructed_pointer)
+                .map_or_else(
+                    || {
+                        Err(Sir0Error::AbsolutePointerOverflow(
+                            absolute_position,
+                            constructed_pointer,
+                        ))
+                    },
+                    Ok,
+                )?;
+            absolute_pointers.push(absolute_position);
+            is_constructing = false;
+            constructed_pointer = 0;
+        } else if current == 0 {
+            break;
+        } else {
+            absolute_position = absolute_position.checked_add(current as u64).map_or_else(
+                || {
+                    Err(Sir0Error::AbsolutePointerOverflow(
+                        absolute_position,
+                        current as u64,
+                    ))
+                },
+                Ok,
+            )?;
+            absolute_pointers.push(absolute_position);
+        }
+    }
+    Ok(absolute_pointers)
 }
 
 /// write the sir0 header at the current position of the file. It should be written at the beggining of the file, but require to know the header and offset list offset.
@@ -156,28 +323,60 @@ pub fn write_sir0_header(
     offset_offset: u32,
 ) -> Result<(), IOError> {
     file.write_all(&[b'S', b'I', b'R', b'0'])?;
-    file.write_u32::<LE>(header_offset)?;
-    file.write_u32::<LE>(offset_offset)?;
+    file.write_u32_le(header_offset)?;
+    file.write_u32_le(offset_offset)?;
     Ok(())
 }
 
 /// An error that occured while writing a sir0 footer
+#[cfg(feature = "std")]
 #[derive(Error, Debug)]
 pub enum Sir0WriteFooterError {
+    /// An error happened while writing the file
     #[error("an error occured while writing the file")]
     IOError(#[from] IOError),
+    /// An element in the list isn't sorted nicely. They need to be smaller from the bigger to the biggest.
     #[error("an element in the isn't sorted nicely. They need to be smaller from the bigger to the biggest. ( {0} is bigger than {1}")]
     NotSorted(u32, u32),
 }
 
-/// Write a sir0 footer, pointing to the various element in the list.
+/// An error that occured while writing a sir0 footer
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub enum Sir0WriteFooterError {
+    /// An error happened while writing the file
+    IOError(IOError),
+    /// An element in the list isn't sorted nicely. They need to be smaller from the bigger to the biggest.
+    NotSorted(u32, u32),
+}
+
+#[cfg(not(feature = "std"))]
+impl From<IOError> for Sir0WriteFooterError {
+    fn from(error: IOError) -> Self {
+        Self::IOError(error)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for Sir0WriteFooterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IOError(_) => write!(f, "an error occured while writing the file"),
+            Self::NotSorted(a, b) => write!(
+                f,
+                "an element in the list isn't sorted nicely. They need to be smaller from the bigger to the biggest. ( {} is smaller than {} )",
+                a, b
+            ),
+        }
+    }
+}
+
+/// Encode a sorted list of absolute positions into the variable-length pointer list used by the sir0 footer.
 /// The element of the list is based on the posititon since the start of the file. For a normal Sir0 file, the first 2 element should be [4, 8]
-pub fn write_sir0_footer<T>(file: &mut T, list: &[u32]) -> Result<(), Sir0WriteFooterError>
-where
-    T: Write,
-{
+pub fn encode_sir0_pointer_list(list: &[u32]) -> Result<Vec<u8>, Sir0WriteFooterError> {
+    let mut output = Vec::new();
     let mut latest_written_pointer = 0;
-    for original_to_write in list.to_owned() {
+    for original_to_write in list.iter().copied() {
         let mut remaining_to_write = original_to_write
             .checked_sub(latest_written_pointer)
             .map_or_else(
@@ -207,11 +406,176 @@ where
         }
         for (counter, value_to_write) in reversed_to_write.iter().cloned().enumerate().rev() {
             if counter == 0 {
-                file.write_all(&[value_to_write])?;
+                output.push(value_to_write);
             } else {
-                file.write_all(&[value_to_write + 0b1000_0000])?;
+                output.push(value_to_write + 0b1000_0000);
             }
         }
     }
+    Ok(output)
+}
+
+/// Write a sir0 footer, pointing to the various element in the list.
+/// The element of the list is based on the posititon since the start of the file. For a normal Sir0 file, the first 2 element should be [4, 8]
+pub fn write_sir0_footer<T>(file: &mut T, list: &[u32]) -> Result<(), Sir0WriteFooterError>
+where
+    T: Write,
+{
+    let encoded = encode_sir0_pointer_list(list)?;
+    file.write_all(&encoded)?;
     Ok(())
 }
+
+/// An error that can occur while writing a Sir0 file with [`Sir0Writer`]
+#[cfg(feature = "std")]
+#[derive(Error, Debug)]
+pub enum Sir0WriterError {
+    /// An error happened while performing an IO operation
+    #[error("an error happened while performing an IO operation")]
+    IOError(#[from] IOError),
+    /// An error happened while writing the footer of the sir0 file
+    #[error("an error happened while writing the footer of the sir0 file")]
+    WriteFooterError(#[from] Sir0WriteFooterError),
+    /// The target or the position of a pointer is too big to fit in an u32
+    #[error("the target or the position of a pointer ({0}) is too big to fit in an u32, the maximal supported value being {1}")]
+    PointerTargetTooBig(u64, u32),
+}
+
+/// An error that can occur while writing a Sir0 file with [`Sir0Writer`]
+#[cfg(not(feature = "std"))]
+#[derive(Debug)]
+pub enum Sir0WriterError {
+    /// An error happened while performing an IO operation
+    IOError(IOError),
+    /// An error happened while writing the footer of the sir0 file
+    WriteFooterError(Sir0WriteFooterError),
+    /// The target or the position of a pointer is too big to fit in an u32
+    PointerTargetTooBig(u64, u32),
+}
+
+#[cfg(not(feature = "std"))]
+impl From<IOError> for Sir0WriterError {
+    fn from(error: IOError) -> Self {
+        Self::IOError(error)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl From<Sir0WriteFooterError> for Sir0WriterError {
+    fn from(error: Sir0WriteFooterError) -> Self {
+        Self::WriteFooterError(error)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for Sir0WriterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IOError(_) => write!(f, "an error happened while performing an IO operation"),
+            Self::WriteFooterError(_) => {
+                write!(f, "an error happened while writing the footer of the sir0 file")
+            }
+            Self::PointerTargetTooBig(target, max) => write!(
+                f,
+                "the target or the position of a pointer ({}) is too big to fit in an u32, the maximal supported value being {}",
+                target, max
+            ),
+        }
+    }
+}
+
+/// A high level writer for Sir0 file, that keep track of the position of the pointers written with [`write_pointer`](Self::write_pointer), so that the footer can be generated automatically.
+///
+/// It wraps a writer `W`, reserving at creation the 12 bytes needed by [`write_sir0_header`]. [`write_payload`](Self::write_payload) let you write arbitrary data, while [`write_header`](Self::write_header) additionally remember the position it is written at, to be used as the header offset of the sir0 file. [`write_pointer`](Self::write_pointer) write an absolute offset toward another part of the file, and record its own position so it is added to the footer. Once everything have been written, call [`finish`](Self::finish) to write the footer then go back to fill the 12 bytes reserved at the beggining.
+#[derive(Debug)]
+pub struct Sir0Writer<W: Write + Seek> {
+    file: W,
+    pointer_positions: Vec<u64>,
+    header_offset: Option<u32>,
+}
+
+impl<W: Write + Seek> Sir0Writer<W> {
+    /// Create a new [`Sir0Writer`], reserving the 12 bytes needed by [`write_sir0_header`] at the current position of `file`.
+    ///
+    /// [`finish`](Self::finish) writes the header at position 0 and all recorded positions are absolute stream
+    /// positions, so the result is only a valid Sir0 file when `file` is at position 0 when this is called.
+    ///
+    /// The header written by [`finish`](Self::finish) itself contains two pointers, at offsets 4 and 8, pointing back
+    /// into the 12 reserved bytes; those are seeded here so the footer always relocates them, without requiring the
+    /// caller to pass them to [`write_pointer`](Self::write_pointer).
+    pub fn new(mut file: W) -> Result<Self, Sir0WriterError> {
+        file.write_all(&[0; 12])?;
+        Ok(Self {
+            file,
+            pointer_positions: vec![4, 8],
+            header_offset: None,
+        })
+    }
+
+    // `stream_position` isn't available on `crate::io_nostd::Seek`, which this must also work against.
+    #[allow(clippy::seek_from_current)]
+    fn position(&mut self) -> Result<u64, IOError> {
+        self.file.seek(SeekFrom::Current(0))
+    }
+
+    /// Write some data, without any special meaning attached to it.
+    pub fn write_payload(&mut self, data: &[u8]) -> Result<(), Sir0WriterError> {
+        self.file.write_all(data)?;
+        Ok(())
+    }
+
+    /// Write the header of the sir0 file, remembering its position so it can be referenced in the 12 bytes written by [`write_sir0_header`] once [`finish`](Self::finish) is called.
+    pub fn write_header(&mut self, header: &[u8]) -> Result<(), Sir0WriterError> {
+        self.header_offset = Some(self.position()? as u32);
+        self.file.write_all(header)?;
+        Ok(())
+    }
+
+    /// Write an absolute pointer toward `target`, and remember the position it was written at, so it is included in the footer generated by [`finish`](Self::finish).
+    pub fn write_pointer(&mut self, target: u64) -> Result<(), Sir0WriterError> {
+        let target_u32 = u32::try_from(target)
+            .map_err(|_| Sir0WriterError::PointerTargetTooBig(target, u32::MAX))?;
+        let position = self.position()?;
+        self.pointer_positions.push(position);
+        self.file.write_u32_le(target_u32)?;
+        Ok(())
+    }
+
+    /// Pad the file with zeroes until the current position is a multiple of `alignment`.
+    pub fn align(&mut self, alignment: u64) -> Result<(), Sir0WriterError> {
+        let position = self.position()?;
+        let remainder = position % alignment;
+        if remainder != 0 {
+            let padding = alignment - remainder;
+            self.file.write_all(&vec![0; padding as usize])?;
+        }
+        Ok(())
+    }
+
+    /// Finish writing the sir0 file: write the footer generated from the pointers recorded by [`write_pointer`](Self::write_pointer), followed by a terminating `0` byte and padding up to the next 16-byte boundary, then seek back to the beggining to write the 12 bytes header with [`write_sir0_header`].
+    ///
+    /// The header offset used is the position recorded by the last call to [`write_header`](Self::write_header), or the current position (the start of the footer) if it was never called.
+    pub fn finish(mut self) -> Result<W, Sir0WriterError> {
+        let offset_offset = self.position()? as u32;
+        let header_offset = self.header_offset.unwrap_or(offset_offset);
+
+        let mut pointer_list = Vec::with_capacity(self.pointer_positions.len());
+        for position in &self.pointer_positions {
+            pointer_list.push(
+                u32::try_from(*position)
+                    .map_err(|_| Sir0WriterError::PointerTargetTooBig(*position, u32::MAX))?,
+            );
+        }
+
+        write_sir0_footer(&mut self.file, &pointer_list)?;
+        // `Sir0::new` always drops the very last byte of the file, assuming it is spent on the
+        // terminator/padding that follows the footer; without writing one, the last real
+        // pointer-list byte would sit at EOF and be lost on read-back.
+        self.write_payload(&[0])?;
+        self.align(16)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        write_sir0_header(&mut self.file, header_offset, offset_offset)?;
+        self.file.seek(SeekFrom::End(0))?;
+        Ok(self.file)
+    }
+}